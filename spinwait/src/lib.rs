@@ -1,20 +1,88 @@
 use std::hint::spin_loop;
 use std::thread::yield_now;
-use std::sync::atomic::{AtomicU32, Ordering};
-use std::thread;
-use std::time::Duration;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::Once;
+use std::time::{Duration, Instant};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+mod spinlock;
+pub use spinlock::{SpinLock, SpinLockGuard};
+
+mod park;
+pub use park::{Parker, ThreadParker};
+
+/// Highest step (inclusive) at which `spin`/`snooze` still execute CPU spin-loop hints.
+const SPIN_LIMIT: u32 = 6;
+
+/// Number of steps after which a backoff is considered exhausted: `is_completed()` becomes
+/// `true` and the caller should stop spinning and block on a real OS primitive instead.
+const YIELD_LIMIT: u32 = 10;
+
+/// Target duration, in nanoseconds, that one "normalized" spin unit should approximate
+/// (a few hundred cycles on a modern multi-GHz core).
+const TARGET_SPIN_NANOS: u64 = 200;
+
+/// Number of `spin_loop()` calls timed in one calibration batch.
+const CALIBRATION_BATCH: u32 = 100_000;
+
+static CALIBRATE_ONCE: Once = Once::new();
+static CALLS_PER_NORMALIZED_UNIT: AtomicU32 = AtomicU32::new(1);
+
+/// Measures how many `spin_loop()` calls approximate `TARGET_SPIN_NANOS` on this CPU, caching
+/// the result for the lifetime of the process.
+///
+/// The measurement runs at most once per process (on first use, via `std::sync::Once`) and is
+/// therefore process-global: it is shared by every `SpinWait` instance, not per-instance.
+fn calls_per_normalized_unit() -> u32 {
+    CALIBRATE_ONCE.call_once(|| {
+        let start = Instant::now();
+        for _ in 0..CALIBRATION_BATCH {
+            spin_loop();
+        }
+        let per_call_nanos = (start.elapsed().as_nanos() / CALIBRATION_BATCH as u128).max(1);
+        let calls = (TARGET_SPIN_NANOS as u128 / per_call_nanos).max(1) as u32;
+        CALLS_PER_NORMALIZED_UNIT.store(calls, Ordering::Relaxed);
+    });
+
+    CALLS_PER_NORMALIZED_UNIT.load(Ordering::Relaxed)
+}
+
+/// Global nonce mixed into each jittered `SpinWait`'s RNG seed, so that two instances created
+/// on the same thread in quick succession don't start from the same state.
+static JITTER_NONCE: AtomicU64 = AtomicU64::new(0);
+
+/// Derives a per-instance xorshift seed from the current thread id and a global nonce.
+fn jitter_seed() -> u64 {
+    let mut hasher = DefaultHasher::new();
+    std::thread::current().id().hash(&mut hasher);
+    let thread_hash = hasher.finish();
+    let nonce = JITTER_NONCE.fetch_add(1, Ordering::Relaxed);
+    (thread_hash ^ nonce.wrapping_mul(0x9E3779B97F4A7C15)) | 1
+}
+
+/// Advances a xorshift64 generator and returns the next value.
+fn xorshift64(state: u64) -> u64 {
+    let mut x = state;
+    x ^= x << 13;
+    x ^= x >> 7;
+    x ^= x << 17;
+    x
+}
 
 /// A lightweight synchronization primitive that spins for short durations before yielding.
 ///
-/// `SpinWait` provides an adaptive spinning mechanism similar to C#’s `SpinWait` type.
-/// It is designed for scenarios where a thread must wait briefly for a condition,
-/// avoiding the overhead of context switching when possible.
+/// `SpinWait` provides an adaptive spinning mechanism similar to C#’s `SpinWait` type and
+/// crossbeam's `Backoff`. It is designed for scenarios where a thread must wait briefly for
+/// a condition, avoiding the overhead of context switching when possible.
 #[derive(Debug)]
 pub struct SpinWait {
-    /// The number of spin iterations performed.
+    /// The number of spin steps performed so far.
     count: AtomicU32,
-    /// The threshold after which spinning yields to the scheduler.
-    yield_threshold: u32,
+    /// xorshift RNG state used to jitter spin counts, when jitter is enabled.
+    jitter_state: AtomicU64,
+    /// Whether spin counts are randomized to avoid lock-step convoys between threads.
+    jitter: bool,
 }
 
 impl SpinWait {
@@ -22,78 +90,205 @@ impl SpinWait {
     pub fn new() -> Self {
         SpinWait {
             count: AtomicU32::new(0),
-            yield_threshold: 10,
+            jitter_state: AtomicU64::new(0),
+            jitter: false,
         }
     }
 
-    /// Creates a new `SpinWait` instance with a custom yield threshold.
-    pub fn with_threshold(yield_threshold: u32) -> Self {
-        Self {
+    /// Creates a new `SpinWait` instance that randomizes its spin counts.
+    ///
+    /// Instead of spinning for exactly `1 << step` hint iterations, each step picks a random
+    /// count uniformly from `[1 << (step - 1), 1 << step]`, using a cheap per-instance xorshift
+    /// generator seeded from the current thread id and a global nonce. When many threads spin
+    /// on the same condition, this spreads their retries out in time instead of letting them
+    /// wake and re-collide on the same cache line in lock-step, while preserving the same
+    /// exponential growth bound as the non-jittered schedule.
+    pub fn with_jitter() -> Self {
+        SpinWait {
             count: AtomicU32::new(0),
-            yield_threshold,
+            jitter_state: AtomicU64::new(jitter_seed()),
+            jitter: true,
         }
     }
 
-    /// Performs a single spin iteration.
+    /// Returns the number of spin-loop hints to issue for the given step, randomizing it
+    /// uniformly within `[1 << (step - 1), 1 << step]` when jitter is enabled.
+    fn hint_count(&self, step: u32) -> u32 {
+        let max = 1u32 << step;
+        if !self.jitter || step == 0 {
+            return max;
+        }
+
+        let min = 1u32 << (step - 1);
+        let state = self.jitter_state.load(Ordering::Relaxed);
+        let next = xorshift64(state);
+        self.jitter_state.store(next, Ordering::Relaxed);
+
+        min + (next % (max - min + 1) as u64) as u32
+    }
+
+    /// Spins for a lock-free retry loop.
     ///
-    /// If the number of iterations exceeds a threshold or the system has a single core,
-    /// this method yields control to the scheduler. Otherwise, it executes a CPU spin hint.
-    pub fn spin_once(&self) {
-        self.count.fetch_add(1, Ordering::Relaxed);
+    /// Use this when retrying a short, uncontended operation (e.g. a single
+    /// `compare_exchange`). It only ever executes CPU `spin_loop()` hints, doubling the
+    /// number of hints on each step up to `SPIN_LIMIT`, and never yields to the scheduler.
+    /// Callers that may need to wait on another thread for longer should use
+    /// [`snooze`](Self::snooze) instead.
+    pub fn spin(&self) {
+        let count = self.count.load(Ordering::Relaxed);
+        for _ in 0..self.hint_count(count.min(SPIN_LIMIT)) {
+            self.spin_once_normalized();
+        }
 
-        // Check if we should yield based on iteration count or core count
-        if self.next_spin_will_yield() || num_cpus::get() == 1 {
-            yield_now();
-        } else if self.count.load(Ordering::Relaxed) < 4 {
+        if count < SPIN_LIMIT {
+            self.count.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Performs one calibrated "normalized" spin unit.
+    ///
+    /// A raw `spin_loop()` hint takes a wildly different amount of wall-clock time across
+    /// microarchitectures, so a fixed hint count gives inconsistent spin durations. This
+    /// method instead issues `calls_per_normalized_unit()` hints, a count calibrated once per
+    /// process to approximate `TARGET_SPIN_NANOS`, so that one logical spin step measures out
+    /// to roughly the same real time regardless of the underlying hardware.
+    pub fn spin_once_normalized(&self) {
+        for _ in 0..calls_per_normalized_unit() {
             spin_loop();
+        }
+    }
+
+    /// Spins while waiting on another thread to make progress.
+    ///
+    /// For steps 0 through `SPIN_LIMIT` this executes CPU `spin_loop()` hints, doubling the
+    /// count each step. After that it calls `yield_now()` to give other threads a chance to
+    /// run, until `YIELD_LIMIT` is reached, at which point [`is_completed`](Self::is_completed)
+    /// returns `true` and further spinning is no longer productive.
+    pub fn snooze(&self) {
+        let count = self.count.load(Ordering::Relaxed);
+
+        if count <= SPIN_LIMIT {
+            for _ in 0..self.hint_count(count) {
+                self.spin_once_normalized();
+            }
         } else {
-            // Short sleep as exponential backoff
-            thread::sleep(Duration::from_nanos(1 << self.count.load(Ordering::Relaxed)));
+            yield_now();
+        }
+
+        if count < YIELD_LIMIT {
+            self.count.fetch_add(1, Ordering::Relaxed);
         }
     }
 
-    /// Returns the number of spin iterations performed.
+    /// Returns the number of spin steps performed.
     pub fn count(&self) -> u32 {
         self.count.load(Ordering::Relaxed)
     }
 
-    /// Indicates whether the next call to `spin_once` will yield control to the scheduler.
+    /// Returns `true` once spinning has reached `YIELD_LIMIT` steps.
     ///
-    /// This is true if the iteration count exceeds the yield threshold or if the system
-    /// has only one physical core, where spinning is less effective.
+    /// At this point further spinning is unlikely to help, and the caller should fall back to
+    /// blocking on a real OS primitive (a mutex, condvar, or thread park) instead.
+    pub fn is_completed(&self) -> bool {
+        self.count.load(Ordering::Relaxed) >= YIELD_LIMIT
+    }
+
+    /// Indicates whether the next call to `snooze` will yield control to the scheduler
+    /// instead of spinning.
     pub fn next_spin_will_yield(&self) -> bool {
-        self.count.load(Ordering::Relaxed) >= self.yield_threshold
+        self.count.load(Ordering::Relaxed) > SPIN_LIMIT
     }
 
-    /// Resets the spin iteration counter to zero.
+    /// Resets the spin step counter to zero.
     pub fn reset(&self) {
         self.count.store(0, Ordering::Relaxed);
     }
 
     /// Spins until the provided condition returns `true`.
     ///
-    /// This method repeatedly calls `spin_once` until the condition is satisfied,
-    /// adapting its behavior based on the number of iterations.
-    ///ß
+    /// This method repeatedly calls `snooze` until the condition is satisfied, adapting its
+    /// behavior based on the number of steps performed so far.
+    ///
     /// # Examples
     /// ```
     /// use spinwait::SpinWait;
+    /// use std::sync::Arc;
     /// use std::sync::atomic::{AtomicBool, Ordering};
+    /// use std::thread;
     ///
-    /// let flag = AtomicBool::new(false);
+    /// let flag = Arc::new(AtomicBool::new(false));
     /// let spinner = SpinWait::new();
+    ///
+    /// let handle = thread::spawn({
+    ///     let flag = flag.clone();
+    ///     move || flag.store(true, Ordering::Relaxed)
+    /// });
+    ///
     /// spinner.spin_until(|| flag.load(Ordering::Relaxed));
+    /// handle.join().unwrap();
     /// ```
     pub fn spin_until<F>(&self, condition: F)
     where
         F: Fn() -> bool,
     {
         while !condition() {
-            self.spin_once();
+            self.snooze();
+        }
+    }
+
+    /// Spins until the condition returns `true`, then parks the thread once spinning is no
+    /// longer productive.
+    ///
+    /// This runs through the staged spin/yield schedule first, same as [`spin_until`]. Once
+    /// [`is_completed`](Self::is_completed) reports that further spinning won't help, it
+    /// instead blocks on `parker` with a short, capped timeout, re-checking `condition` on
+    /// every wake (whether from `unpark` or from the timeout elapsing). This gives callers
+    /// low-latency responses for short waits and zero CPU cost for long ones from one call.
+    pub fn spin_until_or_park<F, P>(&self, condition: F, parker: &P)
+    where
+        F: Fn() -> bool,
+        P: Parker,
+    {
+        while !condition() {
+            if self.is_completed() {
+                parker.park(PARK_TIMEOUT);
+            } else {
+                self.snooze();
+            }
+        }
+    }
+
+    /// Spins until the condition returns `true` or `timeout` elapses, returning whether the
+    /// condition was met.
+    ///
+    /// The deadline is checked only on yield-phase steps (when
+    /// [`next_spin_will_yield`](Self::next_spin_will_yield) is `true`), not on every tight
+    /// spin iteration: apart from the one `Instant::now()` on entry that computes the
+    /// deadline, the spinning steps never read the clock.
+    pub fn spin_until_timeout<F>(&self, condition: F, timeout: Duration) -> bool
+    where
+        F: Fn() -> bool,
+    {
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            if condition() {
+                return true;
+            }
+
+            if self.next_spin_will_yield() && Instant::now() >= deadline {
+                return false;
+            }
+
+            self.snooze();
         }
     }
 }
 
+/// Timeout used by [`SpinWait::spin_until_or_park`] for each park attempt, so a missed
+/// `unpark` can't block the thread past the next re-check of the condition.
+const PARK_TIMEOUT: Duration = Duration::from_millis(10);
+
 impl Default for SpinWait {
     fn default() -> Self {
         Self::new()
@@ -109,18 +304,18 @@ mod tests {
     use std::thread;
 
     #[test]
-    fn test_spin_once_increments_count() {
+    fn test_spin_increments_count() {
         let spinner = SpinWait::new();
         assert_eq!(spinner.count(), 0);
-        spinner.spin_once();
+        spinner.spin();
         assert_eq!(spinner.count(), 1);
     }
 
     #[test]
     fn test_reset_clears_count() {
         let spinner = SpinWait::new();
-        spinner.spin_once();
-        spinner.spin_once();
+        spinner.snooze();
+        spinner.snooze();
         assert_eq!(spinner.count(), 2);
         spinner.reset();
         assert_eq!(spinner.count(), 0);
@@ -129,13 +324,91 @@ mod tests {
     #[test]
     fn test_next_spin_will_yield() {
         let spinner = SpinWait::new();
-        for _ in 0..spinner.yield_threshold {
+        for _ in 0..=SPIN_LIMIT {
             assert!(!spinner.next_spin_will_yield());
-            spinner.spin_once();
+            spinner.snooze();
         }
         assert!(spinner.next_spin_will_yield());
     }
 
+    #[test]
+    fn test_is_completed() {
+        let spinner = SpinWait::new();
+        for _ in 0..YIELD_LIMIT {
+            assert!(!spinner.is_completed());
+            spinner.snooze();
+        }
+        assert!(spinner.is_completed());
+    }
+
+    #[test]
+    fn test_calibration_yields_at_least_one_call() {
+        assert!(calls_per_normalized_unit() >= 1);
+    }
+
+    #[test]
+    fn test_spin_once_normalized_does_not_panic() {
+        let spinner = SpinWait::new();
+        spinner.spin_once_normalized();
+    }
+
+    #[test]
+    fn test_with_jitter_hint_count_stays_in_bounds() {
+        let spinner = SpinWait::with_jitter();
+        for step in 1..=SPIN_LIMIT {
+            let hint = spinner.hint_count(step);
+            assert!(hint >= 1u32 << (step - 1));
+            assert!(hint <= 1u32 << step);
+        }
+    }
+
+    #[test]
+    fn test_spin_until_or_park() {
+        let flag = Arc::new(AtomicBool::new(false));
+        let spinner = SpinWait::new();
+        let parker = crate::ThreadParker::new();
+
+        let handle = thread::spawn({
+            let flag = flag.clone();
+            move || {
+                thread::sleep(std::time::Duration::from_millis(50));
+                flag.store(true, Ordering::Relaxed);
+            }
+        });
+
+        spinner.spin_until_or_park(|| flag.load(Ordering::Relaxed), &parker);
+        assert!(flag.load(Ordering::Relaxed));
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn test_spin_until_timeout_returns_true_when_condition_met() {
+        let flag = Arc::new(AtomicBool::new(false));
+        let spinner = SpinWait::new();
+
+        let handle = thread::spawn({
+            let flag = flag.clone();
+            move || {
+                thread::sleep(std::time::Duration::from_millis(10));
+                flag.store(true, Ordering::Relaxed);
+            }
+        });
+
+        let met = spinner.spin_until_timeout(
+            || flag.load(Ordering::Relaxed),
+            std::time::Duration::from_secs(5),
+        );
+        assert!(met);
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn test_spin_until_timeout_returns_false_when_deadline_passes() {
+        let spinner = SpinWait::new();
+        let met = spinner.spin_until_timeout(|| false, std::time::Duration::from_millis(20));
+        assert!(!met);
+    }
+
     #[test]
     fn test_spin_until() {
         let flag = Arc::new(AtomicBool::new(false));
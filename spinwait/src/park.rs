@@ -0,0 +1,87 @@
+use std::thread::{self, Thread};
+use std::time::Duration;
+
+/// A thread-blocking primitive that [`SpinWait::spin_until_or_park`](crate::SpinWait::spin_until_or_park)
+/// hands off to once spinning stops being productive.
+///
+/// Implementors back a single waiting thread: `park` blocks that thread (for at most
+/// `timeout`), and `unpark` wakes it. The default [`ThreadParker`] does this with
+/// `std::thread::park_timeout`/`Thread::unpark`.
+pub trait Parker {
+    /// Blocks the current thread for up to `timeout`, or until `unpark` is called.
+    fn park(&self, timeout: Duration);
+
+    /// Wakes the thread blocked in `park`, if any.
+    fn unpark(&self);
+}
+
+/// The default [`Parker`], backed by `std::thread::park_timeout`/`Thread::unpark`.
+///
+/// Must be constructed on the thread that will call [`Parker::park`], since it captures that
+/// thread's handle for `unpark` to target.
+#[derive(Debug, Clone)]
+pub struct ThreadParker {
+    thread: Thread,
+}
+
+impl ThreadParker {
+    /// Creates a parker for the current thread.
+    pub fn new() -> Self {
+        ThreadParker {
+            thread: thread::current(),
+        }
+    }
+}
+
+impl Default for ThreadParker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Parker for ThreadParker {
+    fn park(&self, timeout: Duration) {
+        thread::park_timeout(timeout);
+    }
+
+    fn unpark(&self) {
+        self.thread.unpark();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    #[test]
+    fn test_unpark_wakes_parked_thread() {
+        let parker_slot: Arc<std::sync::Mutex<Option<ThreadParker>>> =
+            Arc::new(std::sync::Mutex::new(None));
+        let woken = Arc::new(AtomicBool::new(false));
+
+        let handle = thread::spawn({
+            let parker_slot = parker_slot.clone();
+            let woken = woken.clone();
+            move || {
+                let parker = ThreadParker::new();
+                *parker_slot.lock().unwrap() = Some(parker.clone());
+                parker.park(Duration::from_secs(5));
+                woken.store(true, Ordering::Relaxed);
+            }
+        });
+
+        let parker = loop {
+            if let Some(parker) = parker_slot.lock().unwrap().clone() {
+                break parker;
+            }
+            thread::sleep(Duration::from_millis(1));
+        };
+
+        thread::sleep(Duration::from_millis(10));
+        parker.unpark();
+        handle.join().unwrap();
+        assert!(woken.load(Ordering::Relaxed));
+    }
+}
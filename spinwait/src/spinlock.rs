@@ -0,0 +1,205 @@
+use std::cell::UnsafeCell;
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+
+use crate::SpinWait;
+
+/// A maximum spin budget, in steps, so a lock that has never been contended still gives up
+/// spinning eventually instead of chasing a stale estimate forever.
+const MAX_SPIN_BUDGET: u32 = 32;
+
+/// A test-and-test-and-set spinlock whose spin budget adapts to observed contention.
+///
+/// `SpinLock` is modeled on boost's `spinlock_ttas_adaptive`: it tracks a moving average of
+/// how many spin steps recent successful acquisitions needed, and caps how long `lock()` spins
+/// before falling back to yielding, so an uncontended lock spins briefly while a heavily
+/// contended one gives up spinning fast and lets the scheduler sort things out.
+#[derive(Debug)]
+pub struct SpinLock<T: ?Sized> {
+    locked: AtomicBool,
+    /// Moving average of the spin steps the last few successful acquisitions needed.
+    retries_estimate: AtomicU32,
+    data: UnsafeCell<T>,
+}
+
+unsafe impl<T: ?Sized + Send> Send for SpinLock<T> {}
+unsafe impl<T: ?Sized + Send> Sync for SpinLock<T> {}
+
+impl<T> SpinLock<T> {
+    /// Creates a new unlocked `SpinLock` wrapping `value`.
+    pub fn new(value: T) -> Self {
+        SpinLock {
+            locked: AtomicBool::new(false),
+            retries_estimate: AtomicU32::new(0),
+            data: UnsafeCell::new(value),
+        }
+    }
+
+    /// Consumes the lock, returning the underlying data.
+    pub fn into_inner(self) -> T {
+        self.data.into_inner()
+    }
+}
+
+impl<T: ?Sized> SpinLock<T> {
+    /// Attempts to acquire the lock without spinning, returning `None` if it is held.
+    pub fn try_lock(&self) -> Option<SpinLockGuard<'_, T>> {
+        if self.locked.load(Ordering::Relaxed) {
+            return None;
+        }
+
+        self.locked
+            .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .ok()
+            .map(|_| SpinLockGuard { lock: self })
+    }
+
+    /// Acquires the lock, spinning adaptively until it becomes available.
+    ///
+    /// The test of "test-and-test-and-set" is the relaxed read below: threads first spin on a
+    /// plain load, only attempting the more expensive `compare_exchange` once the lock looks
+    /// free, so contending threads don't hammer the cache line with writes while it's held.
+    /// The spin budget for this call shrinks as the moving average of how many steps recent
+    /// acquisitions needed grows; once that budget is exhausted, `lock()` falls back to
+    /// [`SpinWait::snooze`]. So a lock with little recent contention spins through most of
+    /// `MAX_SPIN_BUDGET` steps, while one under heavy contention gives up spinning almost
+    /// immediately and lets the scheduler sort things out instead.
+    pub fn lock(&self) -> SpinLockGuard<'_, T> {
+        let budget = self.spin_budget();
+        let backoff = SpinWait::new();
+        let mut spins = 0u32;
+
+        loop {
+            if !self.locked.load(Ordering::Relaxed)
+                && self
+                    .locked
+                    .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+                    .is_ok()
+            {
+                self.record_retries(spins);
+                return SpinLockGuard { lock: self };
+            }
+
+            if spins < budget {
+                backoff.spin();
+            } else {
+                backoff.snooze();
+            }
+            spins += 1;
+        }
+    }
+
+    /// Returns how many steps `lock()` should spin before falling back to `snooze()`.
+    ///
+    /// This is `MAX_SPIN_BUDGET` minus the moving average of spins recent acquisitions
+    /// needed: an uncontended lock (estimate near zero) gets nearly the full budget, while a
+    /// heavily contended one (estimate near or above `MAX_SPIN_BUDGET`) gets almost none.
+    fn spin_budget(&self) -> u32 {
+        let contention = self.retries_estimate.load(Ordering::Relaxed).min(MAX_SPIN_BUDGET);
+        MAX_SPIN_BUDGET - contention
+    }
+
+    /// Folds `spins` into the moving average of spin steps successful acquisitions have
+    /// needed, weighting the existing estimate three-to-one against the latest sample.
+    fn record_retries(&self, spins: u32) {
+        let previous = self.retries_estimate.load(Ordering::Relaxed);
+        let updated = (previous * 3 + spins) / 4;
+        self.retries_estimate.store(updated, Ordering::Relaxed);
+    }
+
+    fn unlock(&self) {
+        self.locked.store(false, Ordering::Release);
+    }
+}
+
+impl<T: Default> Default for SpinLock<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+/// An RAII guard that releases a [`SpinLock`] when dropped.
+///
+/// Dereferences to the protected `T`.
+#[derive(Debug)]
+pub struct SpinLockGuard<'a, T: ?Sized> {
+    lock: &'a SpinLock<T>,
+}
+
+impl<T: ?Sized> Deref for SpinLockGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        unsafe { &*self.lock.data.get() }
+    }
+}
+
+impl<T: ?Sized> DerefMut for SpinLockGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.lock.data.get() }
+    }
+}
+
+impl<T: ?Sized> Drop for SpinLockGuard<'_, T> {
+    fn drop(&mut self) {
+        self.lock.unlock();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn test_lock_unlock_roundtrip() {
+        let lock = SpinLock::new(5);
+        {
+            let mut guard = lock.lock();
+            *guard += 1;
+        }
+        assert_eq!(*lock.lock(), 6);
+    }
+
+    #[test]
+    fn test_try_lock_fails_while_held() {
+        let lock = SpinLock::new(0);
+        let guard = lock.lock();
+        assert!(lock.try_lock().is_none());
+        drop(guard);
+        assert!(lock.try_lock().is_some());
+    }
+
+    #[test]
+    fn test_spin_budget_shrinks_as_contention_rises() {
+        let lock = SpinLock::new(0);
+        assert_eq!(lock.spin_budget(), MAX_SPIN_BUDGET);
+
+        for _ in 0..20 {
+            lock.record_retries(MAX_SPIN_BUDGET);
+        }
+        assert!(lock.spin_budget() < MAX_SPIN_BUDGET / 2);
+    }
+
+    #[test]
+    fn test_concurrent_increments() {
+        let lock = Arc::new(SpinLock::new(0));
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let lock = lock.clone();
+                thread::spawn(move || {
+                    for _ in 0..1000 {
+                        *lock.lock() += 1;
+                    }
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(*lock.lock(), 8000);
+    }
+}